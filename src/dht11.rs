@@ -1,7 +1,8 @@
 use core::fmt::Display;
 
-use embassy_time::{Duration, Instant, Timer};
-use esp_hal::gpio::Flex;
+use embassy_time::{Duration, Instant};
+use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal_async::delay::DelayNs;
 
 const COOLDOWN_TIME_MS: u64 = 2000;
 
@@ -14,24 +15,110 @@ pub enum DhtState {
     Cooldown,
 }
 
+/// Which sensor family is wired up: DHT11 or DHT22/AM2302.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensorKind {
+    Dht11,
+    Dht22,
+}
+
+/// Bit-banged DHT11/DHT22 driver, generic over an `embedded-hal` pin and an
+/// `embedded-hal-async` delay provider.
 #[derive(Debug)]
-pub struct Dht11<'a> {
-    pin: Flex<'a>,
+pub struct Dht11<P, D> {
+    pin: P,
+    delay: D,
     data: [u8; 5],
     state: DhtState,
     max_cycles: u32,
     dht_timestamp: u64,
+    kind: SensorKind,
+    last_measurement: Option<(Dht11Measurement, Instant)>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct Dht11Measurement {
     humidity: f32,
     temperature: f32,
 }
 
+/// Temperature unit a [`Dht11Measurement`] can be displayed in, via
+/// [`Dht11Measurement::with_unit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemperatureUnit {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+}
+
 impl Display for Dht11Measurement {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{}% {}°", self.humidity, self.temperature)
+        self.with_unit(TemperatureUnit::Celsius).fmt(f)
+    }
+}
+
+/// Formats a [`Dht11Measurement`] in a caller-chosen [`TemperatureUnit`].
+/// Built via [`Dht11Measurement::with_unit`].
+pub struct Dht11MeasurementDisplay<'a> {
+    measurement: &'a Dht11Measurement,
+    unit: TemperatureUnit,
+}
+
+impl Display for Dht11MeasurementDisplay<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (temperature, symbol) = match self.unit {
+            TemperatureUnit::Celsius => (self.measurement.temperature_celsius(), "°C"),
+            TemperatureUnit::Fahrenheit => (self.measurement.temperature_fahrenheit(), "°F"),
+            TemperatureUnit::Kelvin => (self.measurement.temperature_kelvin(), "K"),
+        };
+
+        write!(
+            f,
+            "{}% {temperature}{symbol}",
+            self.measurement.humidity_percent()
+        )
+    }
+}
+
+impl Dht11Measurement {
+    pub(crate) fn from_raw(humidity: f32, temperature: f32) -> Self {
+        Self {
+            humidity,
+            temperature,
+        }
+    }
+
+    pub fn humidity_percent(&self) -> f32 {
+        self.humidity
+    }
+
+    pub fn temperature_celsius(&self) -> f32 {
+        self.temperature
+    }
+
+    pub fn temperature_fahrenheit(&self) -> f32 {
+        self.temperature * 9.0 / 5.0 + 32.0
+    }
+
+    pub fn temperature_kelvin(&self) -> f32 {
+        self.temperature + 273.15
+    }
+
+    /// Dew point, computed from the Magnus formula:
+    /// `γ = ln(RH/100) + 17.62*T/(243.12+T)`, `Td = 243.12*γ/(17.62-γ)`.
+    pub fn dew_point_celsius(&self) -> f32 {
+        let gamma = libm::logf(self.humidity / 100.0)
+            + 17.62 * self.temperature / (243.12 + self.temperature);
+
+        243.12 * gamma / (17.62 - gamma)
+    }
+
+    /// Returns a [`Display`]-able view of this measurement in `unit`.
+    pub fn with_unit(&self, unit: TemperatureUnit) -> Dht11MeasurementDisplay<'_> {
+        Dht11MeasurementDisplay {
+            measurement: self,
+            unit,
+        }
     }
 }
 
@@ -41,31 +128,67 @@ pub enum Dht11Error {
     ReplyHeaderMissing,
     Timeout,
     Checksum,
+    Pin,
 }
 
-impl<'a> Dht11<'a> {
-    pub fn new(pin: Flex<'a>) -> Dht11<'a> {
+/// Bus turnaround hook for pins that emulate open-drain by toggling an
+/// output/input-enable register (like esp-hal's `Flex`) instead of having
+/// real open-drain hardware. Called once before polling the line for reads,
+/// not on every poll. Pins with genuine open-drain support can no-op.
+pub trait PrepareForRead {
+    fn prepare_for_read(&mut self) {}
+}
+
+impl<P, D> Dht11<P, D>
+where
+    P: InputPin + OutputPin + PrepareForRead,
+    D: DelayNs,
+{
+    pub fn new(pin: P, delay: D) -> Dht11<P, D> {
+        Self::with_kind(pin, delay, SensorKind::Dht11)
+    }
+
+    pub fn with_kind(pin: P, delay: D, kind: SensorKind) -> Dht11<P, D> {
         Self {
             pin,
+            delay,
             data: [0u8; 5],
             state: DhtState::Idle,
             max_cycles: 10000,
             dht_timestamp: 0,
+            kind,
+            last_measurement: None,
         }
     }
 
-    fn read_temperature(&self) -> f32 {
-        let integral_part = self.data[2] as f32;
-        let decimal_part = self.data[3] as f32;
+    /// Returns the last successful measurement and its age, without
+    /// touching the sensor.
+    pub fn last_measurement(&self) -> Option<(Dht11Measurement, Duration)> {
+        self.last_measurement
+            .as_ref()
+            .map(|(m, t)| (*m, Instant::now().duration_since(*t)))
+    }
 
-        integral_part + decimal_part
+    /// Returns the last successful measurement if it's younger than
+    /// `COOLDOWN_TIME_MS`, instead of re-triggering a full acquisition the
+    /// sensor isn't ready for yet. The returned `bool` is `true` when the
+    /// value is the stale cached one rather than a freshly measured one.
+    pub async fn try_measure(&mut self) -> Result<(Dht11Measurement, bool), Dht11Error> {
+        if let Some((m, t)) = &self.last_measurement {
+            if Instant::now().duration_since(*t) < Duration::from_millis(COOLDOWN_TIME_MS) {
+                return Ok((*m, true));
+            }
+        }
+
+        self.measure().await.map(|m| (m, false))
     }
 
-    fn read_humidity(&self) -> f32 {
-        let integral_part = self.data[0] as f32;
-        let decimal_part = self.data[1] as f32;
+    fn read_temperature(&self) -> f32 {
+        decode_temperature(self.kind, &self.data)
+    }
 
-        integral_part + decimal_part
+    fn read_humidity(&self) -> f32 {
+        decode_humidity(self.kind, &self.data)
     }
 
     pub async fn measure(&mut self) -> Result<Dht11Measurement, Dht11Error> {
@@ -76,13 +199,12 @@ impl<'a> Dht11<'a> {
             }
 
             if self.state == DhtState::Cooldown {
-                let temperature = self.read_temperature();
-                let humidity = self.read_humidity();
+                // step() stamps last_measurement when it takes a real reading
+                let (measurement, _) = self
+                    .last_measurement
+                    .expect("a sample is recorded before entering Cooldown");
 
-                return Ok(Dht11Measurement {
-                    humidity,
-                    temperature,
-                });
+                return Ok(measurement);
             }
         }
     }
@@ -91,28 +213,32 @@ impl<'a> Dht11<'a> {
         match self.state {
             DhtState::Idle => self.state = DhtState::Init,
             DhtState::Init => {
-                self.pin.set_high();
-                self.pin.set_output_enable(true);
+                self.pin.set_high().map_err(|_| Dht11Error::Pin)?;
                 self.data = [0u8; 5];
                 self.dht_timestamp = Instant::now().as_millis();
 
-                Timer::after(Duration::from_millis(250)).await;
+                self.delay.delay_ms(250).await;
 
                 self.state = DhtState::BeginMeasurement;
             }
             DhtState::BeginMeasurement => {
                 // start signal
-                self.pin.set_low();
-                self.pin.set_output_enable(true);
+                self.pin.set_low().map_err(|_| Dht11Error::Pin)?;
                 self.dht_timestamp = Instant::now().as_millis();
 
-                Timer::after(Duration::from_millis(20)).await;
+                self.delay.delay_ms(20).await;
                 self.state = DhtState::Read;
             }
             DhtState::Read => {
                 self.dht_timestamp = Instant::now().as_millis();
                 self.state = DhtState::Cooldown;
                 self.read_data().await?;
+
+                let measurement = Dht11Measurement {
+                    humidity: self.read_humidity(),
+                    temperature: self.read_temperature(),
+                };
+                self.last_measurement = Some((measurement, Instant::now()));
             }
             DhtState::Cooldown => {
                 if Instant::now().as_millis() - self.dht_timestamp > COOLDOWN_TIME_MS {
@@ -126,14 +252,12 @@ impl<'a> Dht11<'a> {
     async fn read_data(&mut self) -> Result<(), Dht11Error> {
         let mut cycles = [0u32; 80];
 
-        // end start signal
-        self.pin.set_high();
-        self.pin.set_output_enable(true);
+        // end start signal, release the line so the sensor can pull it low
+        self.pin.set_high().map_err(|_| Dht11Error::Pin)?;
+        self.pin.prepare_for_read();
 
-        self.pin.set_output_enable(false);
-        self.pin.set_input_enable(true);
         // wait to let the sensor pull data line low
-        Timer::after(Duration::from_micros(15)).await;
+        self.delay.delay_us(15).await;
 
         // expect sensor reply:
         // first 80µs low signal
@@ -178,25 +302,21 @@ impl<'a> Dht11<'a> {
     }
 
     fn checksum(&self) -> Result<(), Dht11Error> {
-        if self.data[4] == self.data[0..=3].iter().sum() {
-            Ok(())
-        } else {
-            Err(Dht11Error::Checksum)
-        }
+        checksum(&self.data)
     }
 
-    fn pulse_count(&self, level: bool) -> Result<u32, Dht11Error> {
+    fn pulse_count(&mut self, level: bool) -> Result<u32, Dht11Error> {
         let mut count = 0;
 
         if level {
-            while self.pin.is_high() {
+            while self.pin.is_high().map_err(|_| Dht11Error::Pin)? {
                 count += 1;
                 if count >= self.max_cycles {
                     return Err(Dht11Error::Timeout);
                 }
             }
         } else {
-            while self.pin.is_low() {
+            while self.pin.is_low().map_err(|_| Dht11Error::Pin)? {
                 count += 1;
                 if count >= self.max_cycles {
                     return Err(Dht11Error::Timeout);
@@ -207,3 +327,172 @@ impl<'a> Dht11<'a> {
         Ok(count)
     }
 }
+
+/// Adapts esp-hal's `Flex` to `embedded-hal`'s `InputPin`/`OutputPin`,
+/// emulating open-drain via its output/input-enable registers.
+pub struct OpenDrainFlex<'a>(esp_hal::gpio::Flex<'a>);
+
+impl<'a> OpenDrainFlex<'a> {
+    pub fn new(pin: esp_hal::gpio::Flex<'a>) -> Self {
+        Self(pin)
+    }
+}
+
+impl embedded_hal::digital::ErrorType for OpenDrainFlex<'_> {
+    type Error = core::convert::Infallible;
+}
+
+impl embedded_hal::digital::OutputPin for OpenDrainFlex<'_> {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.0.set_output_enable(true);
+        self.0.set_low();
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.0.set_output_enable(true);
+        self.0.set_high();
+        Ok(())
+    }
+}
+
+impl embedded_hal::digital::InputPin for OpenDrainFlex<'_> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.0.is_high())
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.0.is_low())
+    }
+}
+
+impl PrepareForRead for OpenDrainFlex<'_> {
+    fn prepare_for_read(&mut self) {
+        self.0.set_output_enable(false);
+        self.0.set_input_enable(true);
+    }
+}
+
+/// esp-hal convenience constructors.
+impl<'a> Dht11<OpenDrainFlex<'a>, embassy_time::Delay> {
+    pub fn new_esp_hal(pin: esp_hal::gpio::Flex<'a>) -> Self {
+        Self::new(OpenDrainFlex::new(pin), embassy_time::Delay)
+    }
+
+    pub fn new_esp_hal_with_kind(pin: esp_hal::gpio::Flex<'a>, kind: SensorKind) -> Self {
+        Self::with_kind(OpenDrainFlex::new(pin), embassy_time::Delay, kind)
+    }
+}
+
+/// Decodes the humidity word of a 5-byte DHT frame according to `kind`.
+///
+/// Shared between the bit-banged [`Dht11`] backend and the RMT-based
+/// backend in [`crate::rmt`], since both capture the same 40-bit frame.
+pub(crate) fn decode_humidity(kind: SensorKind, data: &[u8; 5]) -> f32 {
+    match kind {
+        SensorKind::Dht11 => data[0] as f32 + data[1] as f32 * 0.1,
+        SensorKind::Dht22 => {
+            let raw = ((data[0] as u16) << 8) | data[1] as u16;
+            raw as f32 / 10.0
+        }
+    }
+}
+
+/// Decodes the temperature word of a 5-byte DHT frame according to `kind`.
+pub(crate) fn decode_temperature(kind: SensorKind, data: &[u8; 5]) -> f32 {
+    match kind {
+        SensorKind::Dht11 => {
+            let magnitude = data[2] as f32 + (data[3] & 0x7F) as f32 * 0.1;
+
+            if data[3] & 0x80 != 0 {
+                -magnitude
+            } else {
+                magnitude
+            }
+        }
+        SensorKind::Dht22 => {
+            let raw = ((data[2] as u16 & 0x7F) << 8) | data[3] as u16;
+            let magnitude = raw as f32 / 10.0;
+
+            if data[2] & 0x80 != 0 {
+                -magnitude
+            } else {
+                magnitude
+            }
+        }
+    }
+}
+
+pub(crate) fn checksum(data: &[u8; 5]) -> Result<(), Dht11Error> {
+    if data[4] == data[0..=3].iter().sum() {
+        Ok(())
+    } else {
+        Err(Dht11Error::Checksum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 0.01,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn dht11_humidity_decode() {
+        let data = [45, 0, 0, 0, 0];
+        assert_close(decode_humidity(SensorKind::Dht11, &data), 45.0);
+    }
+
+    #[test]
+    fn dht11_temperature_decode_positive() {
+        let data = [0, 0, 25, 3, 0];
+        assert_close(decode_temperature(SensorKind::Dht11, &data), 25.3);
+    }
+
+    #[test]
+    fn dht11_temperature_decode_negative() {
+        // bit 7 of data[3] set means the magnitude is negated
+        let data = [0, 0, 10, 0x80 | 5, 0];
+        assert_close(decode_temperature(SensorKind::Dht11, &data), -10.5);
+    }
+
+    #[test]
+    fn dht22_humidity_decode() {
+        // 0x028C = 652 -> 65.2%
+        let data = [0x02, 0x8C, 0, 0, 0];
+        assert_close(decode_humidity(SensorKind::Dht22, &data), 65.2);
+    }
+
+    #[test]
+    fn dht22_temperature_decode_positive() {
+        // 0x010E = 270 -> 27.0°C
+        let data = [0, 0, 0x01, 0x0E, 0];
+        assert_close(decode_temperature(SensorKind::Dht22, &data), 27.0);
+    }
+
+    #[test]
+    fn dht22_temperature_decode_negative() {
+        // bit 7 of data[2] set means the magnitude is negated
+        let data = [0, 0, 0x80, 0x01, 0];
+        assert_close(decode_temperature(SensorKind::Dht22, &data), -0.1);
+    }
+
+    #[test]
+    fn dew_point_matches_known_reference() {
+        // 25°C at 50% relative humidity -> ~13.85°C dew point
+        let measurement = Dht11Measurement::from_raw(50.0, 25.0);
+        assert_close(measurement.dew_point_celsius(), 13.85);
+    }
+
+    #[test]
+    fn temperature_unit_conversions() {
+        let measurement = Dht11Measurement::from_raw(0.0, 0.0);
+        assert_close(measurement.temperature_fahrenheit(), 32.0);
+        assert_close(measurement.temperature_kelvin(), 273.15);
+    }
+}
@@ -1,4 +1,4 @@
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 
 use dht11::Dht11;
 use embassy_time::{Duration, Timer};
@@ -6,14 +6,16 @@ use esp_hal::gpio::Flex;
 use esp_println::println;
 
 pub mod dht11;
+pub mod rmt;
 
 #[embassy_executor::task]
 pub async fn print_measurements(pin: Flex<'static>) {
-    let mut dht11 = Dht11::new(pin);
+    let mut dht11 = Dht11::new_esp_hal(pin);
 
     loop {
-        match dht11.measure().await {
-            Ok(m) => println!("{m}"),
+        match dht11.try_measure().await {
+            Ok((m, true)) => println!("{m} (stale)"),
+            Ok((m, false)) => println!("{m}"),
             Err(e) => println!("Error: {e:?}"),
         }
 
@@ -0,0 +1,226 @@
+//! Alternative DHT11/DHT22 capture backend using the ESP RMT peripheral
+//! instead of busy-wait cycle counting.
+
+use embassy_time::{Duration, Timer};
+use esp_hal::gpio::Flex;
+use esp_hal::rmt::{PulseCode, RxChannelAsync, RxChannelConfig};
+
+use crate::dht11::{checksum, decode_humidity, decode_temperature};
+use crate::dht11::{Dht11Error, Dht11Measurement, SensorKind};
+
+/// One tick of the RMT receiver, tuned so that 1 tick ≈ 1 µs.
+const RMT_CLK_DIVIDER: u8 = 80; // assumes an 80 MHz RMT source clock
+
+/// High-pulse durations at or above this many microseconds decode to a `1`
+/// bit, shorter ones decode to a `0` bit. Per the datasheet a `0` bit is a
+/// ~26-28µs high pulse and a `1` bit is a ~70µs high pulse.
+const BIT_THRESHOLD_US: u16 = 50;
+
+/// The reply header (80µs low, 80µs high) is a single low-then-high pulse
+/// pair, which the RMT peripheral packs into one `PulseCode` item. Each of
+/// the 40 data bits is likewise one low-then-high pulse pair, so one symbol
+/// per bit follows the header symbol.
+const HEADER_SYMBOLS: usize = 1;
+const DATA_BITS: usize = 40;
+const SYMBOL_BUFFER_LEN: usize = HEADER_SYMBOLS + DATA_BITS + 1;
+
+/// Binds an RMT RX channel to a pin on demand, so the channel used for
+/// capture is always the one configured against the pin `measure()` just
+/// drove, not an independently constructed handle.
+pub trait RxChannelCreator<'a> {
+    type Channel: RxChannelAsync;
+
+    fn configure_rx(
+        &mut self,
+        pin: &mut Flex<'a>,
+        config: RxChannelConfig,
+    ) -> Result<Self::Channel, Dht11Error>;
+}
+
+/// esp-hal's generated channel creators (`rmt.channel0`, `rmt.channel1`,
+/// ...) already expose an inherent `configure_rx(pin, config)` with this
+/// shape; this just adapts their error type to [`Dht11Error`].
+impl<'a> RxChannelCreator<'a> for esp_hal::rmt::ChannelCreator<esp_hal::Blocking, 0> {
+    type Channel = esp_hal::rmt::Channel<esp_hal::Blocking, esp_hal::rmt::Rx, 0>;
+
+    fn configure_rx(
+        &mut self,
+        pin: &mut Flex<'a>,
+        config: RxChannelConfig,
+    ) -> Result<Self::Channel, Dht11Error> {
+        self.configure_rx(pin, config).map_err(|_| Dht11Error::Pin)
+    }
+}
+
+/// DHT11/DHT22 driver variant that captures the sensor reply through an RMT
+/// RX channel rather than bit-banging the data pin from software.
+///
+/// ```no_run
+/// # use dht11_rust_driver::rmt::Dht11Rmt;
+/// # use dht11_rust_driver::dht11::SensorKind;
+/// # fn wire_up(pin: esp_hal::gpio::Flex<'static>, rmt: esp_hal::rmt::Rmt<'static, esp_hal::Blocking>) {
+/// let mut dht11 = Dht11Rmt::new(pin, rmt.channel0, SensorKind::Dht11);
+/// # }
+/// ```
+pub struct Dht11Rmt<'a, C> {
+    pin: Flex<'a>,
+    rx_creator: C,
+    kind: SensorKind,
+}
+
+impl<'a, C> Dht11Rmt<'a, C>
+where
+    C: RxChannelCreator<'a>,
+{
+    pub fn new(pin: Flex<'a>, rx_creator: C, kind: SensorKind) -> Self {
+        Self {
+            pin,
+            rx_creator,
+            kind,
+        }
+    }
+
+    pub async fn measure(&mut self) -> Result<Dht11Measurement, Dht11Error> {
+        // wake the sensor up
+        self.pin.set_high();
+        self.pin.set_output_enable(true);
+        Timer::after(Duration::from_millis(250)).await;
+
+        // start signal
+        self.pin.set_low();
+        Timer::after(Duration::from_millis(20)).await;
+        self.pin.set_high();
+
+        // release the line for the sensor's reply
+        self.pin.set_output_enable(false);
+        self.pin.set_input_enable(true);
+
+        // bind the RMT channel to the same pin we just drove, so the
+        // capture reads the wire the start pulse was issued on instead of
+        // an independently-configured handle to a different GPIO
+        let mut channel = self
+            .rx_creator
+            .configure_rx(
+                &mut self.pin,
+                RxChannelConfig {
+                    clk_divider: RMT_CLK_DIVIDER,
+                    ..Default::default()
+                },
+            )
+            .map_err(|_| Dht11Error::Pin)?;
+
+        let mut symbols = [PulseCode::default(); SYMBOL_BUFFER_LEN];
+        channel
+            .receive(&mut symbols)
+            .await
+            .map_err(|_| Dht11Error::Timeout)?;
+
+        let data = decode_symbols(&symbols)?;
+        checksum(&data)?;
+
+        Ok(Dht11Measurement::from_raw(
+            decode_humidity(self.kind, &data),
+            decode_temperature(self.kind, &data),
+        ))
+    }
+}
+
+/// Classifies the captured RMT symbols into the 5-byte DHT frame.
+///
+/// The first symbol is the sensor's 80µs-low/80µs-high reply header and is
+/// skipped; each of the following 40 symbols carries one data bit, its low
+/// pulse (`length1`) confirming the bit actually arrived and its high pulse
+/// (`length2`) deciding whether it's a `0` or a `1`. A symbol whose low
+/// pulse is missing means the sensor stopped transmitting early (the
+/// unfilled tail of the capture buffer keeps `PulseCode::default()`, i.e.
+/// zero-length pulses), which is reported as a timeout rather than silently
+/// decoded as `0` bits.
+fn decode_symbols(symbols: &[PulseCode]) -> Result<[u8; 5], Dht11Error> {
+    let data_symbols = symbols
+        .get(HEADER_SYMBOLS..HEADER_SYMBOLS + DATA_BITS)
+        .ok_or(Dht11Error::Timeout)?;
+
+    let mut data = [0u8; 5];
+
+    for (i, symbol) in data_symbols.iter().enumerate() {
+        if symbol.length1 == 0 {
+            return Err(Dht11Error::Timeout);
+        }
+
+        data[i / 8] <<= 1;
+
+        if symbol.length2 as u16 >= BIT_THRESHOLD_US {
+            data[i / 8] |= 1;
+        }
+    }
+
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbol(low_us: u16, high_us: u16) -> PulseCode {
+        PulseCode {
+            level1: false,
+            length1: low_us,
+            level2: true,
+            length2: high_us,
+        }
+    }
+
+    fn frame(bits: [bool; DATA_BITS]) -> [PulseCode; SYMBOL_BUFFER_LEN] {
+        let mut symbols = [symbol(50, 28); SYMBOL_BUFFER_LEN];
+
+        for (i, bit) in bits.iter().enumerate() {
+            let high_us = if *bit { 70 } else { 28 };
+            symbols[HEADER_SYMBOLS + i] = symbol(50, high_us);
+        }
+
+        symbols
+    }
+
+    #[test]
+    fn decodes_all_zero_bits() {
+        let symbols = frame([false; DATA_BITS]);
+        assert_eq!(decode_symbols(&symbols).unwrap(), [0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn decodes_all_one_bits() {
+        let symbols = frame([true; DATA_BITS]);
+        assert_eq!(decode_symbols(&symbols).unwrap(), [0xFF; 5]);
+    }
+
+    #[test]
+    fn high_pulse_at_threshold_decodes_as_one() {
+        let mut bits = [false; DATA_BITS];
+        bits[7] = true;
+        let mut symbols = frame(bits);
+        symbols[HEADER_SYMBOLS + 7] = symbol(50, BIT_THRESHOLD_US);
+
+        let data = decode_symbols(&symbols).unwrap();
+        assert_eq!(data[0], 0x01);
+    }
+
+    #[test]
+    fn missing_low_pulse_is_a_timeout() {
+        let mut symbols = frame([false; DATA_BITS]);
+        symbols[HEADER_SYMBOLS + 3] = symbol(0, 0);
+
+        assert!(matches!(
+            decode_symbols(&symbols),
+            Err(Dht11Error::Timeout)
+        ));
+    }
+
+    #[test]
+    fn short_capture_buffer_is_a_timeout() {
+        let symbols = [symbol(50, 28); HEADER_SYMBOLS + DATA_BITS - 1];
+        assert!(matches!(
+            decode_symbols(&symbols),
+            Err(Dht11Error::Timeout)
+        ));
+    }
+}